@@ -1,14 +1,14 @@
-use hir::{db::HirDatabase, AsName};
+use hir::db::HirDatabase;
 use ra_syntax::{
     ast::{self, AstNode},
-    SmolStr, SyntaxElement,
+    SyntaxElement,
     SyntaxKind::{NAME_REF, USE_ITEM},
     SyntaxNode,
 };
 
 use crate::{
     assist_ctx::{ActionBuilder, Assist, AssistCtx},
-    auto_import_text_edit, AssistId, ImportsLocator,
+    AssistId, ImportsLocator,
 };
 
 // Assist: auto_import
@@ -32,15 +32,21 @@ pub(crate) fn auto_import<F: ImportsLocator>(
     ctx: AssistCtx<impl HirDatabase>,
     imports_locator: &mut F,
 ) -> Option<Assist> {
-    let path: ast::Path = ctx.find_node_at_offset()?;
-    let module = path.syntax().ancestors().find_map(ast::Module::cast);
-    let position = match module.and_then(|it| it.item_list()) {
-        Some(item_list) => item_list.syntax().clone(),
+    match ctx.find_node_at_offset::<ast::Path>() {
+        Some(path) => auto_import_for_path(ctx, imports_locator, path),
         None => {
-            let current_file = path.syntax().ancestors().find_map(ast::SourceFile::cast)?;
-            current_file.syntax().clone()
+            let method_call = ctx.find_node_at_offset::<ast::MethodCallExpr>()?;
+            auto_import_trait_for_method_call(ctx, imports_locator, method_call)
         }
-    };
+    }
+}
+
+fn auto_import_for_path<F: ImportsLocator>(
+    ctx: AssistCtx<impl HirDatabase>,
+    imports_locator: &mut F,
+    path: ast::Path,
+) -> Option<Assist> {
+    let position = enclosing_item_list_or_file(path.syntax())?;
     let source_analyzer = ctx.source_analyzer(&position, None);
     let module_with_name_to_import = source_analyzer.module()?;
     let path_to_import = ctx.covering_element().ancestors().find_map(ast::Path::cast)?;
@@ -48,23 +54,90 @@ pub(crate) fn auto_import<F: ImportsLocator>(
         return None;
     }
 
-    let name_to_import = &find_applicable_name_ref(ctx.covering_element())?.as_name();
+    let name_ref = find_applicable_name_ref(ctx.covering_element())?;
     let proposed_imports = imports_locator
-        .find_imports(&name_to_import.to_string())
+        .find_imports(hir::InFile::new(ctx.frange.file_id, &name_ref), module_with_name_to_import)
+        .unwrap_or_default()
         .into_iter()
-        .filter_map(|module_def| module_with_name_to_import.find_use_path(ctx.db, module_def))
         .filter(|use_path| !use_path.segments.is_empty())
         .take(20)
         .collect::<std::collections::HashSet<_>>();
     if proposed_imports.is_empty() {
         return None;
     }
+    let ranked_imports = rank_imports(ctx.db, module_with_name_to_import, proposed_imports);
 
     ctx.add_assist_group(AssistId("auto_import"), "auto import", || {
-        proposed_imports
-            .into_iter()
-            .map(|import| import_to_action(import.to_string(), &position, &path_to_import))
-            .collect()
+        ranked_imports.into_iter().map(|import| import_to_action(import, &position)).collect()
+    })
+}
+
+/// When a method call (`x.foo()`) fails to resolve because the trait
+/// defining `foo` isn't in scope, this proposes importing one of the traits
+/// that do define a matching, applicable method. Operator expressions
+/// (`a + b`) are not handled.
+fn auto_import_trait_for_method_call<F: ImportsLocator>(
+    ctx: AssistCtx<impl HirDatabase>,
+    imports_locator: &mut F,
+    method_call: ast::MethodCallExpr,
+) -> Option<Assist> {
+    let position = enclosing_item_list_or_file(method_call.syntax())?;
+    let source_analyzer = ctx.source_analyzer(&position, None);
+    let module_with_name_to_import = source_analyzer.module()?;
+    if source_analyzer.resolve_method_call(&method_call).is_some() {
+        return None;
+    }
+    let receiver = method_call.expr()?;
+    let receiver_ty = source_analyzer.type_of(ctx.db, &receiver)?;
+    let name_ref = method_call.name_ref()?;
+
+    // The symbol index is searched by the *method's* name, so every hit is a
+    // path to a function (e.g. `std::ops::Debug::fmt`), not to its trait.
+    // Drop the method segment to get the defining trait's own path, which is
+    // what we actually resolve and propose importing.
+    let candidate_traits = imports_locator
+        .find_imports(hir::InFile::new(ctx.frange.file_id, &name_ref), module_with_name_to_import)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(trait_path_for_method)
+        .filter(|trait_path| !trait_path.segments.is_empty())
+        .filter(|trait_path| {
+            module_with_name_to_import
+                .resolve_mod_path(ctx.db, trait_path)
+                .and_then(|def| match def {
+                    hir::ModuleDef::Trait(trait_) => Some(trait_),
+                    _ => None,
+                })
+                .map(|trait_| receiver_ty.impls_trait(ctx.db, trait_, &[]))
+                .unwrap_or(false)
+        })
+        .take(20)
+        .collect::<std::collections::HashSet<_>>();
+    if candidate_traits.is_empty() {
+        return None;
+    }
+    let ranked_imports = rank_imports(ctx.db, module_with_name_to_import, candidate_traits);
+
+    ctx.add_assist_group(AssistId("auto_import"), "auto import", || {
+        ranked_imports.into_iter().map(|import| import_to_action(import, &position)).collect()
+    })
+}
+
+/// Turns a path to a method (`some::Trait::method`) into a path to the
+/// trait that defines it (`some::Trait`).
+fn trait_path_for_method(mut method_path: hir::ModPath) -> Option<hir::ModPath> {
+    if method_path.segments.len() < 2 {
+        return None;
+    }
+    method_path.segments.pop();
+    Some(method_path)
+}
+
+fn enclosing_item_list_or_file(syntax: &SyntaxNode) -> Option<SyntaxNode> {
+    let module = syntax.ancestors().find_map(ast::Module::cast);
+    Some(match module.and_then(|it| it.item_list()) {
+        Some(item_list) => item_list.syntax().clone(),
+        None => syntax.ancestors().find_map(ast::SourceFile::cast)?.syntax().clone(),
     })
 }
 
@@ -83,25 +156,324 @@ fn find_applicable_name_ref(element: SyntaxElement) -> Option<ast::NameRef> {
     }
 }
 
-fn import_to_action(import: String, position: &SyntaxNode, path: &ast::Path) -> ActionBuilder {
+/// Where a proposed import's defining crate sits relative to the crate doing
+/// the importing. Variants are declared in the order we want them to sort:
+/// imports from the current crate first, then direct dependencies, then
+/// `std`/`core`/`alloc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ImportGroup {
+    Current,
+    Dependency,
+    Std,
+}
+
+impl ImportGroup {
+    fn for_crate(
+        db: &impl HirDatabase,
+        importing_crate: hir::Crate,
+        def_crate: hir::Crate,
+    ) -> ImportGroup {
+        if def_crate == importing_crate {
+            ImportGroup::Current
+        } else if is_std_crate(db, def_crate) {
+            ImportGroup::Std
+        } else {
+            ImportGroup::Dependency
+        }
+    }
+}
+
+fn is_std_crate(db: &impl HirDatabase, krate: hir::Crate) -> bool {
+    krate
+        .display_name(db)
+        .map(|name| matches!(name.to_string().as_str(), "std" | "core" | "alloc"))
+        .unwrap_or(false)
+}
+
+/// A proposed import together with the originating crate name to show in its
+/// assist label (when known).
+struct RankedImport {
+    path: hir::ModPath,
+    crate_name: Option<String>,
+}
+
+/// Orders `imports` by [`ImportGroup`], then by path length, then
+/// alphabetically, so the assist list is both deterministic and puts the
+/// most likely candidates (closer to the current crate, shorter paths)
+/// first.
+fn rank_imports(
+    db: &impl HirDatabase,
+    importing_module: hir::Module,
+    imports: std::collections::HashSet<hir::ModPath>,
+) -> Vec<RankedImport> {
+    let scored: Vec<(ImportGroup, hir::ModPath, Option<String>)> = imports
+        .into_iter()
+        .map(|path| {
+            let def_crate = importing_module
+                .resolve_mod_path(db, &path)
+                .and_then(|def| def.module(db))
+                .map(|module| module.krate());
+            let group = def_crate
+                .map(|krate| ImportGroup::for_crate(db, importing_module.krate(), krate))
+                .unwrap_or(ImportGroup::Dependency);
+            let crate_name =
+                def_crate.and_then(|krate| krate.display_name(db)).map(|it| it.to_string());
+            (group, path, crate_name)
+        })
+        .collect();
+    sort_scored_imports(scored)
+        .into_iter()
+        .map(|(_, path, crate_name)| RankedImport { path, crate_name })
+        .collect()
+}
+
+/// Same ordering as [`rank_imports`], split out so it can be unit tested
+/// without a real `HirDatabase`.
+fn sort_scored_imports(
+    mut scored: Vec<(ImportGroup, hir::ModPath, Option<String>)>,
+) -> Vec<(ImportGroup, hir::ModPath, Option<String>)> {
+    scored.sort_by(|(group_a, path_a, _), (group_b, path_b, _)| {
+        group_a
+            .cmp(group_b)
+            .then_with(|| path_a.segments.len().cmp(&path_b.segments.len()))
+            .then_with(|| path_a.to_string().cmp(&path_b.to_string()))
+    });
+    scored
+}
+
+fn import_to_action(import: RankedImport, position: &SyntaxNode) -> ActionBuilder {
+    let path_text = import.path.to_string();
+    let leaf = import
+        .path
+        .segments
+        .last()
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| path_text.clone());
     let mut action_builder = ActionBuilder::default();
-    action_builder.label(format!("Import `{}`", &import));
-    auto_import_text_edit(
-        position,
-        &path.syntax().clone(),
-        &[SmolStr::new(import)],
-        action_builder.text_edit_builder(),
-    );
+    action_builder.label(import_action_label(&leaf, import.crate_name.as_deref()));
+    insert_use::auto_import_text_edit(position, &path_text, action_builder.text_edit_builder());
     action_builder
 }
 
+/// Builds the label an `Import` assist shows in the UI, e.g.
+/// ``Import `HashMap` (from std)``.
+fn import_action_label(leaf: &str, crate_name: Option<&str>) -> String {
+    match crate_name {
+        Some(crate_name) => format!("Import `{}` (from {})", leaf, crate_name),
+        None => format!("Import `{}`", leaf),
+    }
+}
+
+/// A small use-tree manipulation layer used to fold a freshly proposed import
+/// into the `use` items that already exist at the target `position`, instead
+/// of always prepending a brand new standalone statement.
+mod insert_use {
+    use ra_syntax::{
+        ast::{self, AstNode},
+        SyntaxNode, TextSize,
+    };
+    use ra_text_edit::TextEditBuilder;
+
+    /// Inserts a `use` declaration for `target` (a `::`-separated path, e.g.
+    /// `std::collections::HashMap`) somewhere under `container`.
+    ///
+    /// If `target` starts with the parent module of an existing single-path
+    /// `use` item, or with the whole prefix of an existing `{ .. }` group,
+    /// the new leaf is merged into that item, producing (or extending) a
+    /// `{ .. }` group rather than a second, separate `use` statement. A
+    /// shorter shared prefix (only `std` of `std::fmt::Debug` and
+    /// `std::collections::HashMap`) is not merged. Otherwise a new `use`
+    /// item is inserted alphabetically among the existing imports, falling
+    /// back to inserting at the very top of `container` when there are none.
+    pub(crate) fn auto_import_text_edit(
+        container: &SyntaxNode,
+        target: &str,
+        edit: &mut TextEditBuilder,
+    ) {
+        let existing_use_items: Vec<ast::UseItem> =
+            container.children().filter_map(ast::UseItem::cast).collect();
+        let target_segments: Vec<&str> = target.split("::").collect();
+
+        for use_item in &existing_use_items {
+            let use_tree = match use_item.use_tree() {
+                Some(it) => it,
+                None => continue,
+            };
+            let existing_path = match use_tree.path() {
+                Some(it) => it,
+                None => continue,
+            };
+            let existing_segments = flatten_path(&existing_path);
+            let common_len = common_prefix_len(&existing_segments, &target_segments);
+            if common_len == 0 {
+                continue;
+            }
+
+            if let Some(use_tree_list) = use_tree.use_tree_list() {
+                // `use prefix::{a, b, ..};` — fold the new leaf into the
+                // group if the whole group prefix lines up with `target`.
+                if common_len == existing_segments.len() && common_len < target_segments.len() {
+                    let new_leaf = target_segments[common_len..].join("::");
+                    merge_into_existing_group(
+                        use_item,
+                        &existing_path,
+                        &use_tree_list,
+                        &new_leaf,
+                        edit,
+                    );
+                    return;
+                }
+                continue;
+            }
+
+            // Fold into a new `{}` group when `target` shares the existing
+            // item's parent module, however many segments follow it in
+            // `target` — e.g. `std::collections::HashMap` and
+            // `std::collections::hash_map::Entry` share `std::collections`.
+            // An existing item with more than one segment after the shared
+            // module is not merged.
+            let prefix_len = existing_segments.len() - 1;
+            if common_len == prefix_len && target_segments.len() > prefix_len {
+                let existing_leaf = existing_segments[prefix_len].clone();
+                let new_leaf = target_segments[prefix_len..].join("::");
+                merge_into_new_group(
+                    use_item,
+                    &target_segments[..prefix_len],
+                    &existing_leaf,
+                    &new_leaf,
+                    edit,
+                );
+                return;
+            }
+        }
+
+        insert_new_use_item(container, target, &existing_use_items, edit);
+    }
+
+    /// Returns the dotted-path segments of `path`, e.g. `std::collections::HashMap`
+    /// becomes `["std", "collections", "HashMap"]`.
+    fn flatten_path(path: &ast::Path) -> Vec<String> {
+        let mut segments = Vec::new();
+        let mut path = Some(path.clone());
+        while let Some(p) = path {
+            if let Some(name_ref) = p.segment().and_then(|it| it.name_ref()) {
+                segments.push(name_ref.syntax().text().to_string());
+            }
+            path = p.qualifier();
+        }
+        segments.reverse();
+        segments
+    }
+
+    /// The number of leading segments `existing` and `target` have in common.
+    fn common_prefix_len(existing: &[String], target: &[&str]) -> usize {
+        existing.iter().zip(target.iter()).take_while(|(a, b)| a.as_str() == **b).count()
+    }
+
+    fn merge_into_new_group(
+        use_item: &ast::UseItem,
+        prefix: &[&str],
+        existing_leaf: &str,
+        new_leaf: &str,
+        edit: &mut TextEditBuilder,
+    ) {
+        let mut leaves = [existing_leaf, new_leaf];
+        leaves.sort();
+        let new_use = format!("use {}::{{{}, {}}};", prefix.join("::"), leaves[0], leaves[1]);
+        edit.replace(use_item.syntax().text_range(), new_use);
+    }
+
+    fn merge_into_existing_group(
+        use_item: &ast::UseItem,
+        prefix: &ast::Path,
+        use_tree_list: &ast::UseTreeList,
+        new_leaf: &str,
+        edit: &mut TextEditBuilder,
+    ) {
+        // Each member is kept as its full source text, not just its path, so
+        // a nested subtree (`b::{c, d}`) or an `as`-alias isn't collapsed
+        // down to a bare path and silently dropped when the group is rebuilt.
+        let mut members: Vec<String> =
+            use_tree_list.use_trees().map(|tree| tree.syntax().text().to_string()).collect();
+        if !members.iter().any(|member| member == new_leaf) {
+            members.push(new_leaf.to_string());
+        }
+        members.sort();
+        let new_use = format!("use {}::{{{}}};", prefix.syntax().text(), members.join(", "));
+        edit.replace(use_item.syntax().text_range(), new_use);
+    }
+
+    fn insert_new_use_item(
+        container: &SyntaxNode,
+        target: &str,
+        existing_use_items: &[ast::UseItem],
+        edit: &mut TextEditBuilder,
+    ) {
+        let new_use_text = format!("use {};", target);
+        // The indentation of the line `node` starts on, so an inserted line
+        // lines up with the items around it.
+        let indent_of = |node: &SyntaxNode| -> String {
+            node.prev_sibling_or_token()
+                .and_then(|it| it.into_token())
+                .and_then(|ws| ws.text().rfind('\n').map(|pos| ws.text()[pos + 1..].to_string()))
+                .unwrap_or_default()
+        };
+
+        let insert_after = existing_use_items.iter().take_while(|it| {
+            it.use_tree()
+                .and_then(|tree| tree.path())
+                .map(|path| path.syntax().text().to_string().as_str() < target)
+                .unwrap_or(false)
+        });
+        if let Some(last_smaller) = insert_after.last() {
+            let indent = indent_of(last_smaller.syntax());
+            edit.insert(last_smaller.syntax().text_range().end(), format!("\n{}{}", indent, new_use_text));
+            return;
+        }
+
+        if let Some(first_existing) = existing_use_items.first() {
+            let indent = indent_of(first_existing.syntax());
+            edit.insert(first_existing.syntax().text_range().start(), format!("{}\n{}", new_use_text, indent));
+            return;
+        }
+
+        if let Some(first_item) = container.children().next() {
+            let indent = indent_of(&first_item);
+            edit.insert(first_item.text_range().start(), format!("{}\n\n{}", new_use_text, indent));
+            return;
+        }
+
+        let anchor: TextSize = container
+            .first_child_or_token()
+            .map(|it| it.text_range().start())
+            .unwrap_or_else(|| container.text_range().start());
+        edit.insert(anchor, format!("{}\n\n", new_use_text));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::helpers::{
         check_assist_with_imports_locator, check_assist_with_imports_locator_not_applicable,
     };
-    use hir::Name;
+    use hir::{AsName, Name};
+
+    /// Builds a `Name` for an arbitrary identifier, for candidates that
+    /// aren't one of the handful of items `hir::name::known` exposes.
+    fn name(text: &str) -> Name {
+        ast::SourceFile::parse(&format!("use {};", text))
+            .tree()
+            .syntax()
+            .descendants()
+            .find_map(ast::Path::cast)
+            .unwrap()
+            .segment()
+            .unwrap()
+            .name_ref()
+            .unwrap()
+            .as_name()
+    }
 
     #[derive(Clone)]
     struct TestImportsLocator<'a> {
@@ -184,4 +556,312 @@ mod tests {
             "use Debug<|>;",
         );
     }
+
+    #[test]
+    fn merges_into_existing_use_group() {
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut TestImportsLocator::new(&[
+                hir::name::known::std,
+                hir::name::known::collections,
+                hir::name::known::HashMap,
+            ]),
+            "
+            use std::collections::HashSet;
+
+            fn main() {
+                let map = HashMap<|>::new();
+            }",
+            "
+            use std::collections::{HashMap, HashSet};
+
+            fn main() {
+                let map = HashMap<|>::new();
+            }",
+        );
+    }
+
+    #[test]
+    fn merges_into_existing_nested_use_group() {
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut TestImportsLocator::new(&[
+                hir::name::known::std,
+                hir::name::known::collections,
+                hir::name::known::HashMap,
+            ]),
+            "
+            use std::collections::{BTreeSet, HashSet};
+
+            fn main() {
+                let map = HashMap<|>::new();
+            }",
+            "
+            use std::collections::{BTreeSet, HashMap, HashSet};
+
+            fn main() {
+                let map = HashMap<|>::new();
+            }",
+        );
+    }
+
+    #[test]
+    fn merging_into_existing_group_preserves_nested_subtrees() {
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut TestImportsLocator::new(&[name("a"), name("Y")]),
+            "
+            use a::{b::{c, d}, X};
+
+            fn main() {
+                let y: Y<|>;
+            }",
+            "
+            use a::{X, Y, b::{c, d}};
+
+            fn main() {
+                let y: Y<|>;
+            }",
+        );
+    }
+
+    #[test]
+    fn merges_under_shared_parent_module_with_differing_path_lengths() {
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut TestImportsLocator::new(&[
+                hir::name::known::std,
+                hir::name::known::collections,
+                name("hash_map"),
+                name("Entry"),
+            ]),
+            "
+            use std::collections::HashMap;
+
+            fn main() {
+                let x: Entry<|>;
+            }",
+            "
+            use std::collections::{HashMap, hash_map::Entry};
+
+            fn main() {
+                let x: Entry<|>;
+            }",
+        );
+    }
+
+    #[test]
+    fn inserts_alphabetically_among_existing_imports() {
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut TestImportsLocator::new(&[
+                hir::name::known::std,
+                hir::name::known::collections,
+                hir::name::known::HashMap,
+            ]),
+            "
+            use std::fmt::Debug;
+
+            fn main() {
+                let map = HashMap<|>::new();
+            }",
+            "
+            use std::collections::HashMap;
+            use std::fmt::Debug;
+
+            fn main() {
+                let map = HashMap<|>::new();
+            }",
+        );
+    }
+
+    #[test]
+    fn creates_new_use_item_when_none_exist() {
+        let import_path =
+            &[hir::name::known::std, hir::name::known::collections, hir::name::known::HashMap];
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut TestImportsLocator::new(import_path),
+            "
+            fn main() {
+                let map = HashMap<|>::new();
+            }",
+            "
+            use std::collections::HashMap;
+
+            fn main() {
+                let map = HashMap<|>::new();
+            }",
+        );
+    }
+
+    #[test]
+    fn applicable_when_method_call_is_missing_its_trait() {
+        // The locator is queried with the *method's* name_ref and returns a
+        // path to the method itself (`std::ops::Debug::fmt`); the assist
+        // must strip the trailing `fmt` segment before proposing the import.
+        let method_path =
+            &[hir::name::known::std, hir::name::known::ops, hir::name::known::Debug, name("fmt")];
+        let mut imports_locator = TestImportsLocator::new(method_path);
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut imports_locator,
+            "
+            //- /main.rs crate:main deps:std
+            struct Foo;
+            impl std::ops::Debug for Foo {}
+
+            fn main() {
+                let foo = Foo;
+                foo.fmt<|>();
+            }
+            //- /std.rs crate:std
+            pub mod ops {
+                pub trait Debug {
+                    fn fmt(&self) {}
+                }
+            }",
+            "
+            use std::ops::Debug;
+
+            struct Foo;
+            impl std::ops::Debug for Foo {}
+
+            fn main() {
+                let foo = Foo;
+                foo.fmt<|>();
+            }",
+        );
+    }
+
+    struct MultiImportsLocator {
+        import_paths: Vec<Vec<Name>>,
+    }
+
+    impl ImportsLocator for MultiImportsLocator {
+        fn find_imports(
+            &mut self,
+            _: hir::InFile<&ast::NameRef>,
+            _: hir::Module,
+        ) -> Option<Vec<hir::ModPath>> {
+            Some(
+                self.import_paths
+                    .iter()
+                    .map(|segments| hir::ModPath {
+                        kind: hir::PathKind::Plain,
+                        segments: segments.clone(),
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    #[test]
+    fn orders_candidates_alphabetically_within_the_same_group() {
+        // Both candidates genuinely resolve into the `std` crate here, so
+        // they land in the same `ImportGroup::Std` bucket and the tie-break
+        // is purely alphabetical: `std::collections::HashMap` ("c" < "o")
+        // sorts before `std::ops::Debug` and is the one the assist applies.
+        let debug = vec![hir::name::known::std, hir::name::known::ops, hir::name::known::Debug];
+        let hash_map = vec![
+            hir::name::known::std,
+            hir::name::known::collections,
+            hir::name::known::HashMap,
+        ];
+        let mut imports_locator = MultiImportsLocator { import_paths: vec![debug, hash_map] };
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut imports_locator,
+            "
+            //- /main.rs crate:main deps:std
+            fn main() {
+            }
+
+            HashMap<|>
+            //- /std.rs crate:std
+            pub mod collections {
+                pub struct HashMap;
+            }
+            pub mod ops {
+                pub trait Debug {}
+            }",
+            "
+            use std::collections::HashMap;
+
+            fn main() {
+            }
+
+            HashMap<|>",
+        );
+    }
+
+    #[test]
+    fn prefers_current_crate_import_over_a_dependency() {
+        let current_crate_item = vec![name("foo"), name("Thing")];
+        let std_item = vec![name("std"), name("things"), name("Thing")];
+        let mut imports_locator =
+            MultiImportsLocator { import_paths: vec![std_item, current_crate_item] };
+        check_assist_with_imports_locator(
+            auto_import,
+            &mut imports_locator,
+            "
+            //- /main.rs crate:main deps:std
+            mod foo {
+                pub struct Thing;
+            }
+
+            fn main() {
+                let x: Thing<|>;
+            }
+            //- /std.rs crate:std
+            pub mod things {
+                pub struct Thing;
+            }",
+            "
+            use foo::Thing;
+
+            mod foo {
+                pub struct Thing;
+            }
+
+            fn main() {
+                let x: Thing<|>;
+            }",
+        );
+    }
+
+    #[test]
+    fn labels_import_with_its_crate_name() {
+        assert_eq!(import_action_label("HashMap", Some("std")), "Import `HashMap` (from std)");
+        assert_eq!(import_action_label("HashMap", None), "Import `HashMap`");
+    }
+
+    #[test]
+    fn sorts_current_crate_before_dependency_before_std() {
+        let path = |segments: &[Name]| hir::ModPath {
+            kind: hir::PathKind::Plain,
+            segments: segments.to_vec(),
+        };
+        let std_path = path(&[name("std"), name("Thing")]);
+        let dependency_path = path(&[name("some_crate"), name("Thing")]);
+        let current_crate_path = path(&[name("Thing")]);
+
+        let scored = vec![
+            (ImportGroup::Std, std_path.clone(), Some("std".to_string())),
+            (ImportGroup::Dependency, dependency_path.clone(), Some("some_crate".to_string())),
+            (ImportGroup::Current, current_crate_path.clone(), None),
+        ];
+
+        let sorted_paths: Vec<String> =
+            sort_scored_imports(scored).into_iter().map(|(_, path, _)| path.to_string()).collect();
+
+        assert_eq!(
+            sorted_paths,
+            vec![
+                current_crate_path.to_string(),
+                dependency_path.to_string(),
+                std_path.to_string(),
+            ]
+        );
+    }
 }